@@ -6,13 +6,13 @@ use tui::{
 };
 
 use crate::{
-    app::App,
     canvas::{drawing_utils::widget_block, Painter},
+    widgets::App,
 };
 
 impl Painter {
     pub fn draw_basic_network(
-        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+        &self, f: &mut Frame<'_>, app_state: &mut App<'_>, draw_loc: Rect, widget_id: u64,
     ) {
         let divided_loc = Layout::default()
             .direction(Direction::Horizontal)
@@ -44,11 +44,29 @@ impl Painter {
         let total_rx_label = format!("Total RX: {}", app_state.converted_data.total_rx_display);
         let total_tx_label = format!("Total TX: {}", app_state.converted_data.total_tx_display);
 
-        let net_text = vec![
+        let interfaces = &app_state.converted_data.network_interfaces;
+        // The two aggregate lines + one line per interface (RX, then TX) need to fit;
+        // otherwise fall back to just the aggregate view.
+        let has_room_for_interfaces =
+            !interfaces.is_empty() && net_loc[0].height as usize >= 2 + interfaces.len();
+
+        let mut net_text = vec![
             Line::from(Span::styled(rx_label, self.styles.rx_style)),
             Line::from(Span::styled(tx_label, self.styles.tx_style)),
         ];
 
+        if has_room_for_interfaces {
+            for interface in interfaces {
+                net_text.push(Line::from(vec![
+                    Span::styled(
+                        format!("{} RX: {} ", interface.name, interface.rx_display),
+                        self.styles.rx_style,
+                    ),
+                    Span::styled(format!("TX: {}", interface.tx_display), self.styles.tx_style),
+                ]));
+            }
+        }
+
         let total_net_text = vec![
             Line::from(Span::styled(total_rx_label, self.styles.total_rx_style)),
             Line::from(Span::styled(total_tx_label, self.styles.total_tx_style)),
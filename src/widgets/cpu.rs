@@ -0,0 +1,43 @@
+use std::time::Instant;
+
+use sysinfo::{ProcessorExt, System, SystemExt};
+
+use crate::widgets::timed_buffer::Timed;
+
+/// A single core's usage at the time `CPUPackage` was collected.
+#[derive(Clone)]
+pub struct CPUData {
+	pub cpu_name : String,
+	pub cpu_usage : f64,
+}
+
+/// One tick's worth of per-core CPU usage. Despite the name, this holds a
+/// single snapshot (the whole "package" of cores) -- `Data::list_of_cpu_packages`
+/// is what actually accumulates these over time.
+#[derive(Clone)]
+pub struct CPUPackage {
+	pub instant : Instant,
+	pub cpu_list : Vec<CPUData>,
+}
+
+impl Timed for CPUPackage {
+	fn instant(&self) -> Instant {
+		self.instant
+	}
+}
+
+pub fn get_cpu_data_list(sys : &System) -> Result<CPUPackage, heim::Error> {
+	let cpu_list = sys
+		.get_processors()
+		.iter()
+		.map(|processor| CPUData {
+			cpu_name : processor.get_name().to_string(),
+			cpu_usage : processor.get_cpu_usage() as f64,
+		})
+		.collect();
+
+	Ok(CPUPackage {
+		instant : Instant::now(),
+		cpu_list,
+	})
+}
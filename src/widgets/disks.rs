@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use futures::StreamExt;
+use heim::disk;
+use sysinfo::{DiskExt, System};
+
+use crate::widgets::timed_buffer::Timed;
+
+/// A disk's current usage -- not timed, `Data::list_of_disks` is just
+/// overwritten wholesale each tick rather than accumulated.
+#[derive(Clone)]
+pub struct DiskData {
+	pub name : String,
+	pub mount_point : String,
+	pub free_space_in_mb : u64,
+	pub total_space_in_mb : u64,
+}
+
+/// A disk's cumulative read/write byte counters as reported by heim at
+/// collection time (not a per-tick delta), keyed by disk name in `IOPackage`.
+#[derive(Clone)]
+pub struct IOData {
+	pub read_bytes : u64,
+	pub write_bytes : u64,
+}
+
+/// One tick's worth of IO across every disk (or every physical disk, when
+/// collected with `physical = true`).
+#[derive(Clone)]
+pub struct IOPackage {
+	pub instant : Instant,
+	pub io_hash : HashMap<String, IOData>,
+}
+
+impl Timed for IOPackage {
+	fn instant(&self) -> Instant {
+		self.instant
+	}
+}
+
+pub async fn get_disk_usage_list(sys : &System) -> Result<Vec<DiskData>, heim::Error> {
+	Ok(sys
+		.get_disks()
+		.iter()
+		.map(|disk| DiskData {
+			name : disk.get_name().to_string_lossy().to_string(),
+			mount_point : disk.get_mount_point().to_string_lossy().to_string(),
+			free_space_in_mb : disk.get_available_space() / 1024 / 1024,
+			total_space_in_mb : disk.get_total_space() / 1024 / 1024,
+		})
+		.collect())
+}
+
+/// `physical` selects which of heim's two disk-IO counter streams to read:
+/// `io_counters_physical` reports one entry per physical disk, while
+/// `io_counters` reports one per logical partition/mount (so e.g. a disk
+/// with two partitions shows up once in the former and twice in the latter).
+pub async fn get_io_usage_list(physical : bool) -> Result<IOPackage, heim::Error> {
+	let mut io_hash = HashMap::new();
+
+	let mut counters = if physical {
+		disk::io_counters_physical().await?.boxed_local()
+	} else {
+		disk::io_counters().await?.boxed_local()
+	};
+
+	while let Some(counter) = counters.next().await {
+		let counter = counter?;
+		io_hash.insert(
+			counter.device_name().to_string_lossy().to_string(),
+			IOData {
+				read_bytes : counter.read_bytes().get::<heim::units::information::byte>(),
+				write_bytes : counter.write_bytes().get::<heim::units::information::byte>(),
+			},
+		);
+	}
+
+	Ok(IOPackage {
+		instant : Instant::now(),
+		io_hash,
+	})
+}
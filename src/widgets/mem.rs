@@ -0,0 +1,45 @@
+use std::time::Instant;
+
+use sysinfo::{System, SystemExt};
+
+use crate::widgets::timed_buffer::Timed;
+
+/// One tick's worth of memory (or swap) usage.
+#[derive(Clone)]
+pub struct MemData {
+	pub instant : Instant,
+	pub mem_total_in_mb : u64,
+	pub mem_used_in_mb : u64,
+}
+
+impl MemData {
+	pub fn used_percent(&self) -> f64 {
+		if self.mem_total_in_mb == 0 {
+			0.0
+		} else {
+			(self.mem_used_in_mb as f64 / self.mem_total_in_mb as f64) * 100.0
+		}
+	}
+}
+
+impl Timed for MemData {
+	fn instant(&self) -> Instant {
+		self.instant
+	}
+}
+
+pub async fn get_mem_data_list(sys : &System) -> Result<MemData, heim::Error> {
+	Ok(MemData {
+		instant : Instant::now(),
+		mem_total_in_mb : sys.get_total_memory() / 1024,
+		mem_used_in_mb : sys.get_used_memory() / 1024,
+	})
+}
+
+pub async fn get_swap_data_list(sys : &System) -> Result<MemData, heim::Error> {
+	Ok(MemData {
+		instant : Instant::now(),
+		mem_total_in_mb : sys.get_total_swap() / 1024,
+		mem_used_in_mb : sys.get_used_swap() / 1024,
+	})
+}
@@ -3,11 +3,50 @@ pub mod disks;
 pub mod mem;
 pub mod network;
 pub mod processes;
+pub mod recording;
 pub mod temperature;
+pub mod timed_buffer;
 
 use std::collections::HashMap;
+use std::time::Duration;
 use sysinfo::{System, SystemExt};
 
+use recording::Recorder;
+use timed_buffer::retain_fresh;
+
+/// Tracks where a widget was last drawn, so that mouse/selection handling can
+/// map a screen position back to the widget that owns it.
+#[derive(Default, Clone)]
+pub struct WidgetBounds {
+	pub top_left_corner : Option<(u16, u16)>,
+	pub bottom_right_corner : Option<(u16, u16)>,
+}
+
+/// Which widget currently has focus.
+#[derive(Default, Clone)]
+pub struct CurrentWidget {
+	pub widget_id : u64,
+}
+
+/// A single interface's formatted RX/TX rates, ready to be drawn as-is.
+#[derive(Clone)]
+pub struct NetworkInterfaceDisplay {
+	pub name : String,
+	pub rx_display : String,
+	pub tx_display : String,
+}
+
+/// Display-ready strings derived from the raw harvested data, refreshed once
+/// per draw tick so widgets don't need to reformat on every frame.
+#[derive(Default, Clone)]
+pub struct ConvertedData {
+	pub rx_display : String,
+	pub tx_display : String,
+	pub total_rx_display : String,
+	pub total_tx_display : String,
+	pub network_interfaces : Vec<NetworkInterfaceDisplay>,
+}
+
 #[allow(dead_code)]
 pub struct App<'a> {
 	title : &'a str,
@@ -15,6 +54,13 @@ pub struct App<'a> {
 	pub process_sorting_type : processes::ProcessSorting,
 	pub process_sorting_reverse : bool,
 	pub to_be_resorted : bool,
+	pub is_searching : bool,
+	pub process_filter : processes::ProcessFilter,
+	pub current_widget : CurrentWidget,
+	pub converted_data : ConvertedData,
+	pub widget_map : HashMap<u64, WidgetBounds>,
+	pub network_unit_mode : network::NetworkUnitMode,
+	show_bounds : bool,
 }
 
 fn set_if_valid<T : std::clone::Clone>(result : &Result<T, heim::Error>, value_to_set : &mut T) {
@@ -42,13 +88,39 @@ pub struct Data {
 	pub list_of_disks : Vec<disks::DiskData>,            // Only need to keep a list of disks and their data
 }
 
+/// How long to keep collected entries around before they're pruned, one
+/// retention window per kind of timed series (the logical and physical IO
+/// series share `io`). A long network window can sit beside a short CPU
+/// window, for example.
+pub struct RetentionWindows {
+	pub cpu : Duration,
+	pub memory : Duration,
+	pub swap : Duration,
+	pub network : Duration,
+	pub io : Duration,
+}
+
+impl Default for RetentionWindows {
+	fn default() -> Self {
+		let default_retention = Duration::from_secs(60);
+		RetentionWindows {
+			cpu : default_retention,
+			memory : default_retention,
+			swap : default_retention,
+			network : default_retention,
+			io : default_retention,
+		}
+	}
+}
+
 pub struct DataState {
 	pub data : Data,
 	sys : System,
-	stale_max_seconds : u64,
+	retention : RetentionWindows,
 	prev_pid_stats : HashMap<String, f64>,
 	prev_idle : f64,
 	prev_non_idle : f64,
+	recorder : Option<Recorder>,
 }
 
 impl Default for DataState {
@@ -56,96 +128,98 @@ impl Default for DataState {
 		DataState {
 			data : Data::default(),
 			sys : System::new(),
-			stale_max_seconds : 60,
+			retention : RetentionWindows::default(),
 			prev_pid_stats : HashMap::new(),
 			prev_idle : 0_f64,
 			prev_non_idle : 0_f64,
+			recorder : None,
 		}
 	}
 }
 
 impl DataState {
-	pub fn set_stale_max_seconds(&mut self, stale_max_seconds : u64) {
-		self.stale_max_seconds = stale_max_seconds;
+	pub fn set_retention_windows(&mut self, retention : RetentionWindows) {
+		self.retention = retention;
 	}
 
-	pub fn init(&mut self) {
+	/// Starts appending one sample per tick to `path`. Replaces any recorder
+	/// already in progress.
+	pub fn enable_recording(&mut self, path : &str, format : recording::RecordingFormat) -> std::io::Result<()> {
+		self.recorder = Some(Recorder::new(path, format)?);
+		Ok(())
+	}
+
+	pub fn disable_recording(&mut self) {
+		self.recorder = None;
+	}
+
+	/// `recording` is `Some((path, format))` when the user passed a recording
+	/// flag/config option on startup; it's threaded in here (rather than left
+	/// for some later, easy-to-forget call) so a session can't start without
+	/// recording actually being armed.
+	pub fn init(&mut self, recording : Option<(&str, recording::RecordingFormat)>) -> std::io::Result<()> {
 		self.sys.refresh_system();
 		self.sys.refresh_network();
+
+		if let Some((path, format)) = recording {
+			self.enable_recording(path, format)?;
+		}
+
+		Ok(())
 	}
 
-	pub async fn update_data(&mut self) {
+	/// `process_filter` should mirror whatever the user currently has typed
+	/// into `App`'s search box -- the caller is expected to pass `&app.process_filter`
+	/// each tick so a new query takes effect immediately instead of requiring a
+	/// separate, easy-to-forget sync step.
+	pub async fn update_data(&mut self, process_filter : &processes::ProcessFilter) {
 		debug!("Start updating...");
 		self.sys.refresh_system();
 		self.sys.refresh_network();
+		self.sys.refresh_components_list();
+		self.sys.refresh_components();
+		self.sys.refresh_disks_list();
 
 		// What we want to do: For timed data, if there is an error, just do not add.  For other data, just don't update!
 		push_if_valid(&network::get_network_data(&self.sys), &mut self.data.network);
 		push_if_valid(&cpu::get_cpu_data_list(&self.sys), &mut self.data.list_of_cpu_packages);
 
-		// TODO: We can convert this to a multi-threaded task...
-		push_if_valid(&mem::get_mem_data_list().await, &mut self.data.memory);
-		push_if_valid(&mem::get_swap_data_list().await, &mut self.data.swap);
-		set_if_valid(
-			&processes::get_sorted_processes_list(&mut self.prev_idle, &mut self.prev_non_idle, &mut self.prev_pid_stats).await,
-			&mut self.data.list_of_processes,
+		// The rest of the collectors are independent of one another, so run them concurrently
+		// instead of awaiting each in turn -- a slow disk or temperature probe shouldn't hold up
+		// the whole refresh tick. Nothing gets applied to `self.data` until every future here has
+		// resolved, so a partial tick still can't leave `Data` in a half-updated state.
+		let (mem_result, swap_result, process_result, disk_result, io_result, physical_io_result, temperature_result) = futures::join!(
+			mem::get_mem_data_list(&self.sys),
+			mem::get_swap_data_list(&self.sys),
+			processes::get_sorted_processes_list(&self.sys, &mut self.prev_idle, &mut self.prev_non_idle, &mut self.prev_pid_stats, Some(process_filter)),
+			disks::get_disk_usage_list(&self.sys),
+			disks::get_io_usage_list(false),
+			disks::get_io_usage_list(true),
+			temperature::get_temperature_data(&self.sys),
 		);
 
-		set_if_valid(&disks::get_disk_usage_list().await, &mut self.data.list_of_disks);
-		push_if_valid(&disks::get_io_usage_list(false).await, &mut self.data.list_of_io);
-		push_if_valid(&disks::get_io_usage_list(true).await, &mut self.data.list_of_physical_io);
-		set_if_valid(&temperature::get_temperature_data().await, &mut self.data.list_of_temperature);
-
-		// Filter out stale timed entries
-		// TODO: ideally make this a generic function!
-		let current_instant = std::time::Instant::now();
-		self.data.list_of_cpu_packages = self
-			.data
-			.list_of_cpu_packages
-			.iter()
-			.cloned()
-			.filter(|entry| current_instant.duration_since(entry.instant).as_secs() <= self.stale_max_seconds)
-			.collect::<Vec<_>>();
-
-		self.data.memory = self
-			.data
-			.memory
-			.iter()
-			.cloned()
-			.filter(|entry| current_instant.duration_since(entry.instant).as_secs() <= self.stale_max_seconds)
-			.collect::<Vec<_>>();
-
-		self.data.swap = self
-			.data
-			.swap
-			.iter()
-			.cloned()
-			.filter(|entry| current_instant.duration_since(entry.instant).as_secs() <= self.stale_max_seconds)
-			.collect::<Vec<_>>();
-
-		self.data.network = self
-			.data
-			.network
-			.iter()
-			.cloned()
-			.filter(|entry| current_instant.duration_since(entry.instant).as_secs() <= self.stale_max_seconds)
-			.collect::<Vec<_>>();
-
-		self.data.list_of_io = self
-			.data
-			.list_of_io
-			.iter()
-			.cloned()
-			.filter(|entry| current_instant.duration_since(entry.instant).as_secs() <= self.stale_max_seconds)
-			.collect::<Vec<_>>();
-
-		self.data.list_of_physical_io = self
-			.data
-			.list_of_physical_io
-			.iter()
-			.cloned()
-			.filter(|entry| current_instant.duration_since(entry.instant).as_secs() <= self.stale_max_seconds)
-			.collect::<Vec<_>>();
+		push_if_valid(&mem_result, &mut self.data.memory);
+		push_if_valid(&swap_result, &mut self.data.swap);
+		set_if_valid(&process_result, &mut self.data.list_of_processes);
+		set_if_valid(&disk_result, &mut self.data.list_of_disks);
+		push_if_valid(&io_result, &mut self.data.list_of_io);
+		push_if_valid(&physical_io_result, &mut self.data.list_of_physical_io);
+		set_if_valid(&temperature_result, &mut self.data.list_of_temperature);
+
+		// Prune stale timed entries, each series through its own retention window.
+		let now = std::time::Instant::now();
+		self.data.list_of_cpu_packages = retain_fresh(&self.data.list_of_cpu_packages, now, self.retention.cpu);
+		self.data.memory = retain_fresh(&self.data.memory, now, self.retention.memory);
+		self.data.swap = retain_fresh(&self.data.swap, now, self.retention.swap);
+		self.data.network = retain_fresh(&self.data.network, now, self.retention.network);
+		self.data.list_of_io = retain_fresh(&self.data.list_of_io, now, self.retention.io);
+		self.data.list_of_physical_io = retain_fresh(&self.data.list_of_physical_io, now, self.retention.io);
+
+		if let Some(recorder) = &mut self.recorder {
+			if let Err(err) = recorder.record(&self.data) {
+				debug!("Failed to record tick: {}", err);
+			}
+		}
 
 		debug!("End updating...");
 	}
@@ -159,11 +233,89 @@ impl<'a> App<'a> {
 			should_quit : false,
 			process_sorting_reverse : true,
 			to_be_resorted : false,
+			is_searching : false,
+			process_filter : processes::ProcessFilter::default(),
+			current_widget : CurrentWidget::default(),
+			converted_data : ConvertedData::default(),
+			widget_map : HashMap::new(),
+			network_unit_mode : network::NetworkUnitMode::default(),
+			show_bounds : false,
 		}
 	}
 
+	pub fn should_get_widget_bounds(&self) -> bool {
+		self.show_bounds
+	}
+
+	/// Recomputes `converted_data` from the latest harvested network tick,
+	/// including the per-interface breakdown. Call once per draw tick, after
+	/// `DataState::update_data` has run.
+	pub fn refresh_converted_network_data(&mut self, network_history : &[network::NetworkData]) {
+		let latest = match network_history.last() {
+			Some(latest) => latest,
+			None => return,
+		};
+
+		let unit_mode = self.network_unit_mode;
+		self.converted_data.rx_display = network::format_network_rate(latest.rx, unit_mode);
+		self.converted_data.tx_display = network::format_network_rate(latest.tx, unit_mode);
+		self.converted_data.total_rx_display = network::format_network_rate(latest.total_rx, unit_mode);
+		self.converted_data.total_tx_display = network::format_network_rate(latest.total_tx, unit_mode);
+
+		let mut interfaces : Vec<_> = latest.interfaces.iter().collect();
+		interfaces.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		self.converted_data.network_interfaces = interfaces
+			.into_iter()
+			.map(|(name, (rx, tx))| NetworkInterfaceDisplay {
+				name : name.clone(),
+				rx_display : network::format_network_rate(*rx, unit_mode),
+				tx_display : network::format_network_rate(*tx, unit_mode),
+			})
+			.collect();
+	}
+
 	pub fn on_key(&mut self, c : char) {
+		if self.is_searching {
+			match c {
+				'\u{1b}' => {
+					// Escape: discard the in-progress query and leave search mode.
+					self.process_filter.query.clear();
+					self.is_searching = false;
+					self.to_be_resorted = true;
+				}
+				'\u{8}' | '\u{7f}' => {
+					// Backspace: edit the query in place.
+					self.process_filter.query.pop();
+					self.to_be_resorted = true;
+				}
+				'\n' | '\r' => {
+					// Enter: keep the query applied and leave search mode.
+					self.is_searching = false;
+				}
+				_ => {
+					self.process_filter.query.push(c);
+					self.to_be_resorted = true;
+				}
+			}
+			return;
+		}
+
 		match c {
+			'/' => {
+				self.is_searching = true;
+			}
+			'u' => {
+				self.network_unit_mode = self.network_unit_mode.cycle();
+			}
+			'i' => {
+				self.process_filter.case_insensitive = !self.process_filter.case_insensitive;
+				self.to_be_resorted = true;
+			}
+			'x' => {
+				self.process_filter.use_regex = !self.process_filter.use_regex;
+				self.to_be_resorted = true;
+			}
 			'q' => self.should_quit = true,
 			'h' => self.on_right(),
 			'j' => self.on_down(),
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use sysinfo::{NetworkExt, System, SystemExt};
+
+use crate::widgets::timed_buffer::Timed;
+
+/// How a network rate (bytes/sec) should be presented to the user.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NetworkUnitMode {
+	/// Binary bytes, e.g. `1.00MiB/s`.
+	BinaryBytes,
+	/// Decimal bytes, e.g. `1.00MB/s`.
+	DecimalBytes,
+	/// Decimal bits, e.g. `8.00Mbps`, matching how ISPs advertise speeds.
+	Bits,
+}
+
+impl Default for NetworkUnitMode {
+	fn default() -> Self {
+		NetworkUnitMode::BinaryBytes
+	}
+}
+
+impl NetworkUnitMode {
+	/// Cycles through the available unit modes, used by the keybind that lets
+	/// users compare against ISP bits-based numbers live.
+	pub fn cycle(self) -> NetworkUnitMode {
+		match self {
+			NetworkUnitMode::BinaryBytes => NetworkUnitMode::DecimalBytes,
+			NetworkUnitMode::DecimalBytes => NetworkUnitMode::Bits,
+			NetworkUnitMode::Bits => NetworkUnitMode::BinaryBytes,
+		}
+	}
+}
+
+/// Formats a rate in bytes/sec according to the given unit mode, e.g.
+/// `format_network_rate(1_048_576, NetworkUnitMode::BinaryBytes) == "1.00MiB/s"`.
+pub fn format_network_rate(bytes_per_sec : u64, unit_mode : NetworkUnitMode) -> String {
+	match unit_mode {
+		NetworkUnitMode::BinaryBytes => format_with_units(bytes_per_sec as f64, 1024.0, &["B", "KiB", "MiB", "GiB", "TiB"], "/s"),
+		NetworkUnitMode::DecimalBytes => format_with_units(bytes_per_sec as f64, 1000.0, &["B", "KB", "MB", "GB", "TB"], "/s"),
+		NetworkUnitMode::Bits => format_with_units(bytes_per_sec as f64 * 8.0, 1000.0, &["b", "Kb", "Mb", "Gb", "Tb"], "ps"),
+	}
+}
+
+fn format_with_units(mut value : f64, base : f64, units : &[&str], suffix : &str) -> String {
+	let mut unit_index = 0;
+	while value >= base && unit_index < units.len() - 1 {
+		value /= base;
+		unit_index += 1;
+	}
+
+	format!("{:.2}{}{}", value, units[unit_index], suffix)
+}
+
+/// Aggregate and per-interface network throughput for a single collection
+/// tick. `rx`/`tx` are totals across all interfaces; `interfaces` keeps the
+/// same two numbers broken out by interface name (e.g. `eth0`, `wlan0`, `lo`)
+/// so widgets can optionally render a finer-grained view.
+#[derive(Clone)]
+pub struct NetworkData {
+	pub instant : Instant,
+	pub rx : u64,
+	pub tx : u64,
+	pub total_rx : u64,
+	pub total_tx : u64,
+	pub interfaces : HashMap<String, (u64, u64)>, // interface name -> (rx, tx)
+}
+
+impl Timed for NetworkData {
+	fn instant(&self) -> Instant {
+		self.instant
+	}
+}
+
+pub fn get_network_data(sys : &System) -> Result<NetworkData, heim::Error> {
+	let mut rx = 0;
+	let mut tx = 0;
+	let mut total_rx = 0;
+	let mut total_tx = 0;
+	let mut interfaces = HashMap::new();
+
+	for (name, data) in sys.get_networks() {
+		// `get_received`/`get_transmitted` are the delta since the last refresh (i.e. this
+		// tick's rate); `get_total_received`/`get_total_transmitted` are the genuine running
+		// total sysinfo has tracked since it started monitoring this interface.
+		let iface_rx = data.get_received();
+		let iface_tx = data.get_transmitted();
+		rx += iface_rx;
+		tx += iface_tx;
+		total_rx += data.get_total_received();
+		total_tx += data.get_total_transmitted();
+		interfaces.insert(name.clone(), (iface_rx, iface_tx));
+	}
+
+	Ok(NetworkData {
+		instant : Instant::now(),
+		rx,
+		tx,
+		total_rx,
+		total_tx,
+		interfaces,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn binary_bytes_rolls_over_at_1024() {
+		assert_eq!(format_network_rate(1_048_576, NetworkUnitMode::BinaryBytes), "1.00MiB/s");
+	}
+
+	#[test]
+	fn decimal_bytes_rolls_over_at_1000() {
+		assert_eq!(format_network_rate(1_000_000, NetworkUnitMode::DecimalBytes), "1.00MB/s");
+	}
+
+	#[test]
+	fn bits_mode_multiplies_by_eight() {
+		assert_eq!(format_network_rate(125_000, NetworkUnitMode::Bits), "1.00Mbps");
+	}
+
+	#[test]
+	fn cycle_wraps_back_to_binary_bytes() {
+		let mode = NetworkUnitMode::BinaryBytes.cycle().cycle().cycle();
+		assert!(mode == NetworkUnitMode::BinaryBytes);
+	}
+}
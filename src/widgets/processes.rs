@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use sysinfo::{ProcessExt, System, SystemExt};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProcessSorting {
+	CPU,
+	MEM,
+	PID,
+	NAME,
+}
+
+#[derive(Clone)]
+pub struct ProcessData {
+	pub pid : u32,
+	pub name : String,
+	pub cpu_usage_percent : f64,
+	pub mem_usage_percent : f64,
+}
+
+/// A process-name query built up from the search keybind. Matching is a
+/// simple case-sensitive substring check by default; `case_insensitive`
+/// ignores case, and `use_regex` switches it to treat `query` as a regular
+/// expression instead.
+#[derive(Clone, Default)]
+pub struct ProcessFilter {
+	pub query : String,
+	pub case_insensitive : bool,
+	pub use_regex : bool,
+}
+
+impl ProcessFilter {
+	/// Compiles the regex (if any) once; pass the result into every `matches`
+	/// call for this tick instead of recompiling per process.
+	pub fn compile(&self) -> Option<Regex> {
+		if !self.use_regex || self.query.is_empty() {
+			return None;
+		}
+
+		let pattern = if self.case_insensitive {
+			format!("(?i){}", self.query)
+		} else {
+			self.query.clone()
+		};
+
+		Regex::new(&pattern).ok()
+	}
+
+	/// Lowercases `query` once (for the case-insensitive substring path); pass
+	/// the result into every `matches` call for this tick instead of
+	/// relowercasing per process.
+	pub fn lowercase_query(&self) -> Option<String> {
+		if self.use_regex || self.query.is_empty() || !self.case_insensitive {
+			return None;
+		}
+
+		Some(self.query.to_lowercase())
+	}
+
+	pub fn matches(&self, process_name : &str, compiled_regex : Option<&Regex>, lowercase_query : Option<&str>) -> bool {
+		if self.query.is_empty() {
+			return true;
+		}
+
+		if self.use_regex {
+			return compiled_regex.map_or(false, |re| re.is_match(process_name));
+		}
+
+		if let Some(lowercase_query) = lowercase_query {
+			process_name.to_lowercase().contains(lowercase_query)
+		} else {
+			process_name.contains(&self.query)
+		}
+	}
+}
+
+pub async fn get_sorted_processes_list(
+	sys : &System, prev_idle : &mut f64, prev_non_idle : &mut f64, prev_pid_stats : &mut HashMap<String, f64>, filter : Option<&ProcessFilter>,
+) -> Result<Vec<ProcessData>, heim::Error> {
+	let total_memory = sys.get_total_memory().max(1) as f64;
+	let mut total_idle = 0_f64;
+	let mut total_non_idle = 0_f64;
+	prev_pid_stats.clear();
+
+	let processes : Vec<ProcessData> = sys
+		.get_processes()
+		.iter()
+		.map(|(pid, process)| {
+			let cpu_usage_percent = process.cpu_usage() as f64;
+			let mem_usage_percent = (process.memory() as f64 / total_memory) * 100.0;
+
+			if cpu_usage_percent > 0.0 {
+				total_non_idle += cpu_usage_percent;
+			} else {
+				total_idle += 1.0;
+			}
+
+			prev_pid_stats.insert(pid.to_string(), cpu_usage_percent);
+
+			ProcessData {
+				pid : *pid as u32,
+				name : process.name().to_string(),
+				cpu_usage_percent,
+				mem_usage_percent,
+			}
+		})
+		.collect();
+
+	*prev_idle = total_idle;
+	*prev_non_idle = total_non_idle;
+
+	Ok(match filter {
+		Some(filter) => {
+			let compiled_regex = filter.compile();
+			let lowercase_query = filter.lowercase_query();
+			processes
+				.into_iter()
+				.filter(|process| filter.matches(&process.name, compiled_regex.as_ref(), lowercase_query.as_deref()))
+				.collect()
+		}
+		None => processes,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty_query_matches_everything() {
+		let filter = ProcessFilter::default();
+		assert!(filter.matches("anything", filter.compile().as_ref(), filter.lowercase_query().as_deref()));
+	}
+
+	#[test]
+	fn substring_match_is_case_sensitive_by_default() {
+		let filter = ProcessFilter {
+			query : "firefox".to_string(),
+			..ProcessFilter::default()
+		};
+
+		assert!(filter.matches("firefox", filter.compile().as_ref(), filter.lowercase_query().as_deref()));
+		assert!(!filter.matches("Firefox", filter.compile().as_ref(), filter.lowercase_query().as_deref()));
+	}
+
+	#[test]
+	fn case_insensitive_substring_match() {
+		let filter = ProcessFilter {
+			query : "firefox".to_string(),
+			case_insensitive : true,
+			..ProcessFilter::default()
+		};
+
+		assert!(filter.matches("Firefox", filter.compile().as_ref(), filter.lowercase_query().as_deref()));
+	}
+
+	#[test]
+	fn lowercase_query_is_precomputed_once_for_the_case_insensitive_path() {
+		let filter = ProcessFilter {
+			query : "FireFox".to_string(),
+			case_insensitive : true,
+			..ProcessFilter::default()
+		};
+
+		assert_eq!(filter.lowercase_query().as_deref(), Some("firefox"));
+	}
+
+	#[test]
+	fn regex_mode_compiles_once_and_matches() {
+		let filter = ProcessFilter {
+			query : "^fire.*$".to_string(),
+			use_regex : true,
+			..ProcessFilter::default()
+		};
+
+		let compiled_regex = filter.compile();
+		let lowercase_query = filter.lowercase_query();
+		assert!(filter.matches("firefox", compiled_regex.as_ref(), lowercase_query.as_deref()));
+		assert!(!filter.matches("chromium", compiled_regex.as_ref(), lowercase_query.as_deref()));
+	}
+}
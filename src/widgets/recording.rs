@@ -0,0 +1,128 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use super::Data;
+
+/// On-disk format for a recorded session.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+	Csv,
+	Json,
+}
+
+/// One tick's worth of the data worth keeping around for offline analysis.
+/// We don't dump the full `Data` struct verbatim -- most of it is transient
+/// per-process/per-disk detail that's only useful live -- just the headline
+/// numbers someone would want to chart spikes in afterwards.
+#[derive(Serialize)]
+struct RecordedSample {
+	unix_timestamp_secs : u64,
+	network_rx_bytes : u64,
+	network_tx_bytes : u64,
+	memory_used_percent : f64,
+	process_count : usize,
+}
+
+/// Appends one `RecordedSample` per tick to a file, so a monitoring session
+/// can be replayed or graphed elsewhere without screen-scraping the TUI.
+pub struct Recorder {
+	writer : File,
+	format : RecordingFormat,
+	wrote_header : bool,
+}
+
+impl Recorder {
+	pub fn new(path : &str, format : RecordingFormat) -> io::Result<Recorder> {
+		let writer = OpenOptions::new().create(true).append(true).open(path)?;
+		// An append-only recorder re-opened against an existing file must not
+		// re-emit the CSV header partway through the data -- only write it for
+		// a genuinely empty (i.e. brand new) file.
+		let wrote_header = writer.metadata()?.len() > 0;
+
+		Ok(Recorder { writer, format, wrote_header })
+	}
+
+	pub fn record(&mut self, data : &Data) -> io::Result<()> {
+		let sample = RecordedSample {
+			unix_timestamp_secs : SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+			network_rx_bytes : data.network.last().map_or(0, |entry| entry.rx),
+			network_tx_bytes : data.network.last().map_or(0, |entry| entry.tx),
+			memory_used_percent : data.memory.last().map_or(0.0, |entry| entry.used_percent()),
+			process_count : data.list_of_processes.len(),
+		};
+
+		match self.format {
+			RecordingFormat::Json => {
+				let line = serde_json::to_string(&sample).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+				writeln!(self.writer, "{}", line)
+			}
+			RecordingFormat::Csv => {
+				if !self.wrote_header {
+					writeln!(self.writer, "unix_timestamp_secs,network_rx_bytes,network_tx_bytes,memory_used_percent,process_count")?;
+					self.wrote_header = true;
+				}
+				writeln!(
+					self.writer,
+					"{},{},{},{},{}",
+					sample.unix_timestamp_secs, sample.network_rx_bytes, sample.network_tx_bytes, sample.memory_used_percent, sample.process_count
+				)
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Read;
+
+	use super::*;
+
+	fn temp_path(name : &str) -> std::path::PathBuf {
+		std::env::temp_dir().join(format!("bottom-recording-test-{}-{}", std::process::id(), name))
+	}
+
+	#[test]
+	fn csv_header_is_written_once_for_a_new_file() {
+		let path = temp_path("new.csv");
+		let _ = std::fs::remove_file(&path);
+
+		let mut recorder = Recorder::new(path.to_str().unwrap(), RecordingFormat::Csv).unwrap();
+		recorder.record(&Data::default()).unwrap();
+		recorder.record(&Data::default()).unwrap();
+
+		let mut contents = String::new();
+		File::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+		let header_count = contents.lines().filter(|line| line.starts_with("unix_timestamp_secs")).count();
+
+		assert_eq!(header_count, 1);
+		std::fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn csv_header_is_not_repeated_when_reopening_an_existing_file() {
+		let path = temp_path("existing.csv");
+		let _ = std::fs::remove_file(&path);
+
+		Recorder::new(path.to_str().unwrap(), RecordingFormat::Csv)
+			.unwrap()
+			.record(&Data::default())
+			.unwrap();
+
+		// Re-opening the same path (as would happen if recording is re-enabled
+		// mid-session) must not write the header again.
+		Recorder::new(path.to_str().unwrap(), RecordingFormat::Csv)
+			.unwrap()
+			.record(&Data::default())
+			.unwrap();
+
+		let mut contents = String::new();
+		File::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+		let header_count = contents.lines().filter(|line| line.starts_with("unix_timestamp_secs")).count();
+
+		assert_eq!(header_count, 1);
+		std::fs::remove_file(&path).unwrap();
+	}
+}
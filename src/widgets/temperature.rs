@@ -0,0 +1,20 @@
+use sysinfo::{ComponentExt, System, SystemExt};
+
+/// A single sensor reading. Not a timed series -- like `DiskData`,
+/// `Data::list_of_temperature` is overwritten wholesale each tick.
+#[derive(Clone)]
+pub struct TempData {
+	pub component_name : String,
+	pub temperature_celsius : f32,
+}
+
+pub async fn get_temperature_data(sys : &System) -> Result<Vec<TempData>, heim::Error> {
+	Ok(sys
+		.get_components()
+		.iter()
+		.map(|component| TempData {
+			component_name : component.label().to_string(),
+			temperature_celsius : component.temperature(),
+		})
+		.collect())
+}
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+/// Anything collected on a tick and timestamped with the `Instant` it was
+/// collected at. Implemented by each of the timed data series (CPU, memory,
+/// network, ...) so they can all be pruned through the same code path
+/// instead of repeating the same `filter` by hand.
+pub trait Timed {
+	fn instant(&self) -> Instant;
+}
+
+/// Drops entries older than `retention` relative to `now`, keeping the
+/// ordering of the remaining entries. Replaces the old copy-pasted
+/// `filter(|entry| now.duration_since(entry.instant) <= stale_max_seconds)`
+/// blocks -- each series can now pass its own retention window instead of
+/// sharing a single global one.
+pub fn retain_fresh<T: Timed + Clone>(entries : &[T], now : Instant, retention : Duration) -> Vec<T> {
+	entries
+		.iter()
+		.cloned()
+		.filter(|entry| now.duration_since(entry.instant()) <= retention)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Clone)]
+	struct Entry(Instant);
+
+	impl Timed for Entry {
+		fn instant(&self) -> Instant {
+			self.0
+		}
+	}
+
+	#[test]
+	fn drops_entries_older_than_retention() {
+		let now = Instant::now();
+		let entries = vec![
+			Entry(now - Duration::from_secs(120)),
+			Entry(now - Duration::from_secs(30)),
+			Entry(now),
+		];
+
+		let fresh = retain_fresh(&entries, now, Duration::from_secs(60));
+
+		assert_eq!(fresh.len(), 2);
+	}
+
+	#[test]
+	fn keeps_everything_within_retention() {
+		let now = Instant::now();
+		let entries = vec![Entry(now - Duration::from_secs(1)), Entry(now)];
+
+		let fresh = retain_fresh(&entries, now, Duration::from_secs(60));
+
+		assert_eq!(fresh.len(), 2);
+	}
+}